@@ -1,10 +1,95 @@
+use std::collections::{HashMap, VecDeque};
+
 use crate::{movement::Direction, Range, RopeSlice, Selection, Syntax};
 use tree_sitter::{Node, Tree};
 
-pub fn expand_selection(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+/// A lightweight fingerprint of a [`Selection`], cheap enough to keep one
+/// around per history entry so we can tell whether the selection has been
+/// changed by something other than `expand_selection`/`shrink_selection`
+/// since we recorded it (a document edit, a search jump, etc).
+type SelectionFingerprint = Box<[(usize, usize)]>;
+
+fn fingerprint(selection: &Selection) -> SelectionFingerprint {
+    selection
+        .ranges()
+        .iter()
+        .map(|range| (range.anchor, range.head))
+        .collect()
+}
+
+/// The pre-expansion selection pushed by one `expand_selection` call, along
+/// with a fingerprint of the selection that call produced, so a later
+/// `shrink_selection` can detect that the selection moved on without it and
+/// refuse to restore a stale entry.
+struct HistoryEntry {
+    selection: Selection,
+    fingerprint: SelectionFingerprint,
+}
+
+/// Per-(document, view) stacks of pre-expansion selections.
+///
+/// `expand_selection` pushes the selection it is about to transform onto the
+/// stack for its key; `shrink_selection` pops the top entry and restores it
+/// verbatim instead of re-deriving a child node heuristically, so a chain of
+/// expands can always be undone exactly. `pop` clears a stack as soon as the
+/// current selection no longer matches the one its top entry's expand
+/// produced, e.g. after the selection is changed by anything other than
+/// expand/shrink or moved by an edit. An edit that leaves the selection's
+/// positions unchanged is not detected this way; `invalidate` drops a key's
+/// history explicitly.
+#[derive(Default)]
+pub struct SelectionHistory {
+    stacks: HashMap<u64, Vec<HistoryEntry>>,
+}
+
+impl SelectionHistory {
+    /// Records `selection` as the pre-expansion state for `key`, to be
+    /// restored the next time `pop` is called with a `current` selection
+    /// matching `resulting`, i.e. the selection `expand_selection` produced
+    /// from it.
+    pub fn push(&mut self, key: u64, selection: Selection, resulting: &Selection) {
+        self.stacks.entry(key).or_default().push(HistoryEntry {
+            fingerprint: fingerprint(resulting),
+            selection,
+        });
+    }
+
+    /// Pops and returns the most recently pushed selection for `key`,
+    /// provided `current` still matches the `resulting` selection it was
+    /// pushed with. If it doesn't match (the document reparsed or the selection
+    /// moved some other way), the stack for `key` is discarded entirely so
+    /// we never restore a range against a tree it was never part of.
+    pub fn pop(&mut self, key: u64, current: &Selection) -> Option<Selection> {
+        let stack = self.stacks.get_mut(&key)?;
+        let top = stack.last()?;
+
+        if top.fingerprint != fingerprint(current) {
+            stack.clear();
+            return None;
+        }
+
+        stack.pop().map(|entry| entry.selection)
+    }
+
+    /// Drops the history for `key`, e.g. when a non expand/shrink command
+    /// has changed the selection and any pending entries would no longer
+    /// correspond to it.
+    pub fn invalidate(&mut self, key: u64) {
+        self.stacks.remove(&key);
+    }
+}
+
+pub fn expand_selection(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    history: (&mut SelectionHistory, u64),
+) -> Selection {
+    let (history, key) = history;
+
     let cursor = &mut syntax.walk();
 
-    selection.transform(|range| {
+    let expanded = selection.clone().transform(|range| {
         let from = text.char_to_byte(range.from());
         let to = text.char_to_byte(range.to());
 
@@ -22,10 +107,24 @@ pub fn expand_selection(syntax: &Syntax, text: RopeSlice, selection: Selection)
         let to = text.byte_to_char(node.end_byte());
 
         Range::new(to, from).with_direction(range.direction())
-    })
+    });
+
+    history.push(key, selection, &expanded);
+
+    expanded
 }
 
-pub fn shrink_selection(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+pub fn shrink_selection(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    history: (&mut SelectionHistory, u64),
+) -> Selection {
+    let (history, key) = history;
+    if let Some(restored) = history.pop(key, &selection) {
+        return restored;
+    }
+
     selection.transform(move |range| {
         let (from, to) = range.into_byte_range(text);
         let mut cursor = syntax.walk();
@@ -68,30 +167,91 @@ fn find_parent_with_more_children(mut node: Node) -> Option<Node> {
 }
 
 pub fn select_all_siblings(tree: &Tree, text: RopeSlice, selection: Selection) -> Selection {
+    select_all_siblings_impl(tree, text, selection, false)
+}
+
+/// Like [`select_all_siblings`], but only keeps siblings whose tree-sitter
+/// `kind()` matches the node currently under the cursor, e.g. to select
+/// every `match` arm of a `match` expression while skipping the separators
+/// and comments in between.
+pub fn select_all_siblings_same_kind(
+    tree: &Tree,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
+    select_all_siblings_impl(tree, text, selection, true)
+}
+
+fn select_all_siblings_impl(
+    tree: &Tree,
+    text: RopeSlice,
+    selection: Selection,
+    same_kind: bool,
+) -> Selection {
     let root_node = &tree.root_node();
 
     selection.transform_iter(|range| {
         let from = text.char_to_byte(range.from());
         let to = text.char_to_byte(range.to());
 
+        let kind_filter = same_kind
+            .then(|| root_node.descendant_for_byte_range(from, to))
+            .flatten()
+            .map(|node| node.kind_id());
+
         root_node
             .descendant_for_byte_range(from, to)
             .and_then(find_parent_with_more_children)
-            .and_then(|parent| select_children(parent, text, range.direction()))
+            .and_then(|parent| select_children(parent, text, range.direction(), kind_filter))
             .unwrap_or_else(|| vec![range].into_iter())
     })
 }
 
 pub fn select_all_children(tree: &Tree, text: RopeSlice, selection: Selection) -> Selection {
+    select_all_children_impl(tree, text, selection, false)
+}
+
+/// Like [`select_all_children`], but only keeps children whose tree-sitter
+/// `kind()` matches that of the node's first named child, e.g. to select
+/// every `match_arm` of a `match` expression while skipping the separators
+/// and comments in between.
+pub fn select_all_children_same_kind(
+    tree: &Tree,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
+    select_all_children_impl(tree, text, selection, true)
+}
+
+fn select_all_children_impl(
+    tree: &Tree,
+    text: RopeSlice,
+    selection: Selection,
+    same_kind: bool,
+) -> Selection {
     let root_node = &tree.root_node();
 
     selection.transform_iter(|range| {
         let from = text.char_to_byte(range.from());
         let to = text.char_to_byte(range.to());
 
-        root_node
-            .descendant_for_byte_range(from, to)
-            .and_then(|parent| select_children(parent, text, range.direction()))
+        let node = root_node.descendant_for_byte_range(from, to);
+        // The filter must come from one of `node`'s children, not from
+        // `node` itself: `node` is the parent whose children we're about to
+        // select, and a child sharing its own parent's kind is rare, so
+        // filtering against the parent's kind degenerated to an empty
+        // selection almost always. The first named child stands in for
+        // "the kind of entry this node is made of".
+        let kind_filter = same_kind
+            .then(|| {
+                node.and_then(|parent| {
+                    let mut cursor = parent.walk();
+                    parent.named_children(&mut cursor).next().map(|c| c.kind_id())
+                })
+            })
+            .flatten();
+
+        node.and_then(|parent| select_children(parent, text, range.direction(), kind_filter))
             .unwrap_or_else(|| vec![range].into_iter())
     })
 }
@@ -100,11 +260,13 @@ fn select_children(
     node: Node,
     text: RopeSlice,
     direction: Direction,
+    kind_filter: Option<u16>,
 ) -> Option<<Vec<Range> as std::iter::IntoIterator>::IntoIter> {
     let mut cursor = node.walk();
 
     let children = node
         .named_children(&mut cursor)
+        .filter(|child| kind_filter.map_or(true, |kind_id| child.kind_id() == kind_id))
         .map(|child| {
             let from = text.byte_to_char(child.start_byte());
             let to = text.byte_to_char(child.end_byte());
@@ -124,6 +286,79 @@ fn select_children(
     }
 }
 
+/// Which way [`select_node_by_kind`] should walk the tree from the current
+/// range while looking for a node of the requested kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKindDirection {
+    /// Walk up through parents, stopping at the first match (e.g. "jump to
+    /// the enclosing function").
+    Ancestor,
+    /// Breadth-first scan through named descendants, stopping at the first
+    /// match (e.g. "jump into the first string literal").
+    Descendant,
+}
+
+/// Selects the nearest node whose `kind()` equals `kind_name`, searching
+/// either up through ancestors or down through descendants of the node at
+/// the current range. Returns the range unchanged if no such node exists.
+pub fn select_node_by_kind(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    kind_name: &str,
+    direction: NodeKindDirection,
+) -> Selection {
+    selection.transform(|range| {
+        let (from, to) = range.into_byte_range(text);
+        let mut cursor = syntax.walk();
+        cursor.reset_to_byte_range(from, to);
+
+        let found = match direction {
+            NodeKindDirection::Ancestor => find_ancestor_by_kind(cursor.node(), kind_name),
+            NodeKindDirection::Descendant => find_descendant_by_kind(cursor.node(), kind_name),
+        };
+
+        match found {
+            Some(node) => Range::from_node(node, text, range.direction()),
+            None => range,
+        }
+    })
+}
+
+/// Walks up from `node` (including `node` itself) through its parents,
+/// returning the first one whose `kind()` equals `kind_name`, or `None` if the root is reached without a
+/// match. Split out of [`select_node_by_kind`] so the walk can be
+/// unit-tested directly against a [`Tree`] without needing a [`Syntax`].
+fn find_ancestor_by_kind<'a>(mut node: Node<'a>, kind_name: &str) -> Option<Node<'a>> {
+    loop {
+        if node.kind() == kind_name {
+            return Some(node);
+        }
+        node = node.parent()?;
+    }
+}
+
+/// Breadth-first search through `node`'s named descendants (including
+/// `node` itself), returning the first one whose `kind()` equals
+/// `kind_name`, or `None` if none match. Split out of
+/// [`select_node_by_kind`] so the walk can be unit-tested directly against
+/// a [`Tree`] without needing a [`Syntax`].
+fn find_descendant_by_kind<'a>(node: Node<'a>, kind_name: &str) -> Option<Node<'a>> {
+    let mut queue = VecDeque::new();
+    queue.push_back(node);
+
+    while let Some(node) = queue.pop_front() {
+        if node.kind() == kind_name {
+            return Some(node);
+        }
+
+        let mut cursor = node.walk();
+        queue.extend(node.named_children(&mut cursor));
+    }
+
+    None
+}
+
 pub fn select_prev_sibling(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
     selection.transform(move |range| {
         let (from, to) = range.into_byte_range(text);
@@ -139,3 +374,180 @@ pub fn select_prev_sibling(syntax: &Syntax, text: RopeSlice, selection: Selectio
         Range::from_node(cursor.node(), text, range.direction())
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_pop_round_trip_test() {
+        let mut history = SelectionHistory::default();
+        let key = 0;
+
+        let base = Selection::single(0, 1);
+        let expanded_once = Selection::single(0, 5);
+        let expanded_twice = Selection::single(0, 10);
+
+        // expand_selection: push the pre-expansion selection, keyed by the
+        // fingerprint of what it expanded to.
+        history.push(key, base.clone(), &expanded_once);
+        history.push(key, expanded_once.clone(), &expanded_twice);
+
+        // shrink_selection: pop restores the most recent pre-expansion
+        // selection exactly, provided `current` matches what was pushed.
+        assert_eq!(
+            history.pop(key, &expanded_twice),
+            Some(expanded_once.clone())
+        );
+        assert_eq!(history.pop(key, &expanded_once), Some(base.clone()));
+
+        // The stack is now empty.
+        assert_eq!(history.pop(key, &base), None);
+    }
+
+    #[test]
+    fn invalidate_on_foreign_selection_change_test() {
+        let mut history = SelectionHistory::default();
+        let key = 0;
+
+        let base = Selection::single(0, 1);
+        let expanded = Selection::single(0, 5);
+        history.push(key, base, &expanded);
+
+        // A selection change that didn't go through expand_selection (a
+        // document edit, a search jump, etc) doesn't match the fingerprint
+        // recorded at push time, so pop must refuse to restore it...
+        let foreign = Selection::single(2, 3);
+        assert_eq!(history.pop(key, &foreign), None);
+
+        // ...and must discard the stack entirely rather than leaving a stale
+        // entry behind for a later pop to restore against the wrong tree.
+        assert_eq!(history.pop(key, &expanded), None);
+    }
+
+    #[test]
+    fn invalidate_clears_stack_test() {
+        let mut history = SelectionHistory::default();
+        let key = 0;
+
+        let base = Selection::single(0, 1);
+        let expanded = Selection::single(0, 5);
+        history.push(key, base, &expanded);
+
+        history.invalidate(key);
+
+        assert_eq!(history.pop(key, &expanded), None);
+    }
+
+    /// Parses `source` as Rust and returns the rope backing it alongside
+    /// the resulting tree, for tests that exercise the tree-sitter-backed
+    /// selection helpers directly.
+    fn parse_rust(source: &str) -> (ropey::Rope, Tree) {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::language())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        (ropey::Rope::from_str(source), tree)
+    }
+
+    /// A single-range `Selection` spanning the first occurrence of `needle`
+    /// in `source`.
+    fn select(source: &str, needle: &str) -> Selection {
+        let from = source.find(needle).unwrap();
+        Selection::single(from, from + needle.len())
+    }
+
+    const MATCH_SOURCE: &str = "fn f() {\n    match x {\n        A => 1,\n        // comment\n        B => 2,\n        C => 3,\n    }\n}";
+
+    #[test]
+    fn select_all_siblings_same_kind_skips_comments_test() {
+        let (rope, tree) = parse_rust(MATCH_SOURCE);
+        let text = rope.slice(..);
+        let selection = select(MATCH_SOURCE, "A => 1");
+
+        let result = select_all_siblings_same_kind(&tree, text, selection);
+
+        let selected: Vec<_> = result
+            .ranges()
+            .iter()
+            .map(|range| text.slice(range.from()..range.to()).to_string())
+            .collect();
+        assert_eq!(selected, vec!["A => 1", "B => 2", "C => 3"]);
+    }
+
+    #[test]
+    fn select_all_siblings_same_kind_no_siblings_is_noop_test() {
+        let source = "fn f() {}";
+        let (rope, tree) = parse_rust(source);
+        let text = rope.slice(..);
+        let selection = select(source, "fn f() {}");
+
+        let result = select_all_siblings_same_kind(&tree, text, selection.clone());
+
+        assert_eq!(result, selection);
+    }
+
+    #[test]
+    fn select_all_children_same_kind_skips_comments_test() {
+        let (rope, tree) = parse_rust(MATCH_SOURCE);
+        let text = rope.slice(..);
+        // Put the cursor on the match block (the `{ ... }` containing the
+        // arms), whose named children are the three `match_arm`s plus the
+        // interleaved comment.
+        let selection = select(
+            MATCH_SOURCE,
+            "{\n        A => 1,\n        // comment\n        B => 2,\n        C => 3,\n    }",
+        );
+
+        let result = select_all_children_same_kind(&tree, text, selection);
+
+        let selected: Vec<_> = result
+            .ranges()
+            .iter()
+            .map(|range| text.slice(range.from()..range.to()).to_string())
+            .collect();
+        assert_eq!(selected, vec!["A => 1", "B => 2", "C => 3"]);
+    }
+
+    #[test]
+    fn find_ancestor_by_kind_match_test() {
+        let (_rope, tree) = parse_rust(MATCH_SOURCE);
+        let arm = tree
+            .root_node()
+            .descendant_for_byte_range(
+                MATCH_SOURCE.find("A => 1").unwrap(),
+                MATCH_SOURCE.find("A => 1").unwrap() + "A => 1".len(),
+            )
+            .unwrap();
+
+        let function = find_ancestor_by_kind(arm, "function_item");
+        assert_eq!(function.map(|n| n.kind()), Some("function_item"));
+    }
+
+    #[test]
+    fn find_ancestor_by_kind_no_match_test() {
+        let (_rope, tree) = parse_rust(MATCH_SOURCE);
+        let root = tree.root_node();
+
+        assert_eq!(find_ancestor_by_kind(root, "does_not_exist"), None);
+    }
+
+    #[test]
+    fn find_descendant_by_kind_match_test() {
+        let (_rope, tree) = parse_rust(MATCH_SOURCE);
+
+        let arm = find_descendant_by_kind(tree.root_node(), "match_arm");
+        assert_eq!(arm.map(|n| n.kind()), Some("match_arm"));
+    }
+
+    #[test]
+    fn find_descendant_by_kind_no_match_test() {
+        let (_rope, tree) = parse_rust(MATCH_SOURCE);
+
+        assert_eq!(
+            find_descendant_by_kind(tree.root_node(), "does_not_exist"),
+            None
+        );
+    }
+}
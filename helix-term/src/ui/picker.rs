@@ -1,3 +1,4 @@
+mod frecency;
 mod handlers;
 mod query;
 
@@ -15,7 +16,7 @@ use crate::{
 };
 use futures_util::future::BoxFuture;
 use helix_event::AsyncHook;
-use nucleo::pattern::{CaseMatching, Normalization};
+use nucleo::pattern::{Atom, AtomKind, CaseMatching, Normalization};
 use nucleo::{Config, Nucleo, Utf32String};
 use thiserror::Error;
 use tokio::sync::mpsc::Sender;
@@ -30,7 +31,8 @@ use tui::widgets::Widget;
 
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
     io::Read,
     path::{Path, PathBuf},
     sync::{
@@ -54,14 +56,27 @@ use helix_view::{
 
 use super::overlay::Overlay;
 
-use self::handlers::PreviewHighlightHandler;
+use self::{frecency::FrecencyStore, handlers::PreviewHighlightHandler};
 
 pub const ID: &str = "picker";
 
 pub const MIN_AREA_WIDTH_FOR_PREVIEW: u16 = 72;
+/// Minimum terminal height for a preview pane rendered below the picker list.
+pub const MIN_AREA_HEIGHT_FOR_PREVIEW: u16 = 20;
 /// Biggest file size to preview in bytes
 pub const MAX_FILE_SIZE_FOR_PREVIEW: u64 = 10 * 1024 * 1024;
 
+/// Where the preview pane is placed relative to the picker's list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerLayout {
+    /// Preview to the right of the list. The default, and the only sensible
+    /// choice on wide-short terminals.
+    Horizontal,
+    /// Preview below the list, so a tall-narrow terminal doesn't have to
+    /// squeeze both side by side.
+    Vertical,
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub enum PathOrId {
     Id(DocumentId),
@@ -98,10 +113,127 @@ pub type FileLocation = (PathOrId, Option<(usize, usize)>);
 pub enum CachedPreview {
     Document(Box<Document>),
     Binary,
+    /// A hex/offset/ASCII dump of the first chunk of the file (whatever was
+    /// already read into `read_buffer` for content-type detection).
+    HexDump(String),
+    /// Binary data detected (by magic bytes) as an image, bounded by
+    /// `MAX_FILE_SIZE_FOR_PREVIEW` like every other preview. Kept as its own
+    /// variant rather than folded into the hex dump so the image case stays
+    /// visible and distinguishable from generic binary data. Rendered via the
+    /// kitty graphics protocol when the backend supports it and the bytes are
+    /// PNG (see `render_preview`'s `CachedPreview::Image` branch); falls back
+    /// to the placeholder text otherwise. Sixel and non-PNG formats (e.g.
+    /// JPEG, which kitty's raw passthrough transmission can't decode without
+    /// pulling in an image-decoding dependency) are intentionally out of
+    /// scope for now and also fall back to the placeholder.
+    Image(Vec<u8>),
     LargeFile,
     NotFound,
 }
 
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Checks the leading bytes against common image format magic numbers, so
+/// the picker can distinguish an image from generic binary data even when it
+/// can't actually draw that particular format (see `is_png`).
+fn is_image(bytes: &[u8]) -> bool {
+    bytes.starts_with(PNG_MAGIC) || bytes.starts_with(&[0xFF, 0xD8, 0xFF])
+}
+
+/// Whether `bytes` are specifically PNG, the only format `kitty_image_escape`
+/// can hand to the terminal without decoding it ourselves first.
+fn is_png(bytes: &[u8]) -> bool {
+    bytes.starts_with(PNG_MAGIC)
+}
+
+/// Best-effort detection of a terminal that understands the kitty graphics
+/// protocol (kitty itself, and wezterm which implements the same protocol).
+/// There's no portable query-and-wait-for-reply we can do from inside a
+/// synchronous render call, so this is a heuristic based on the environment
+/// variables those terminals are known to set, same as other editors/TUIs
+/// that support this protocol rely on.
+fn kitty_graphics_supported() -> bool {
+    std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var("TERM_PROGRAM").is_ok_and(|program| program == "WezTerm")
+}
+
+/// Builds the kitty graphics protocol escape sequence that transmits and
+/// immediately displays a PNG image (format `100`, the only transmission
+/// format kitty can decode itself) scaled to `cols` by `rows` terminal cells.
+///
+/// Payloads are base64-encoded and chunked to at most 4096 bytes per chunk,
+/// as required by the protocol; `m=1` marks all but the final chunk as
+/// non-terminal.
+fn kitty_image_escape(png_bytes: &[u8], cols: u16, rows: u16) -> String {
+    const CHUNK_SIZE: usize = 4096;
+
+    let payload = base64_encode(png_bytes);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(CHUNK_SIZE).collect();
+    let mut escape = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 == chunks.len() { 0 } else { 1 };
+        if i == 0 {
+            escape.push_str(&format!(
+                "\x1b_Ga=T,f=100,c={cols},r={rows},m={more};",
+            ));
+        } else {
+            escape.push_str(&format!("\x1b_Gm={more};"));
+        }
+        escape.push_str(std::str::from_utf8(chunk).expect("base64 output is ASCII"));
+        escape.push_str("\x1b\\");
+    }
+    escape
+}
+
+/// Minimal base64 (standard alphabet, with padding) encoder so transmitting
+/// an image doesn't need to pull in a dependency just for this.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[((b0 & 0x03) << 4 | b1.unwrap_or(0) >> 4) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => ALPHABET[((b1 & 0x0f) << 2 | b2.unwrap_or(0) >> 6) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => ALPHABET[(b2 & 0x3f) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+/// Renders all of `bytes` (the at most 1 KB read for content-type detection,
+/// at its call site) as a classic offset / hex / ASCII triplet, 16 bytes per
+/// row.
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let offset = i * 16;
+        let hex: String = chunk.iter().map(|byte| format!("{byte:02x} ")).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        out.push_str(&format!("{offset:08x}  {hex:<48}  {ascii}\n"));
+    }
+    out
+}
+
 // We don't store this enum in the cache so as to avoid lifetime constraints
 // from borrowing a document already opened in the editor.
 pub enum Preview<'picker, 'editor> {
@@ -125,6 +257,8 @@ impl Preview<'_, '_> {
             Self::Cached(preview) => match preview {
                 CachedPreview::Document(_) => "<Invalid file location>",
                 CachedPreview::Binary => "<Binary file>",
+                CachedPreview::HexDump(_) => "<Binary file>",
+                CachedPreview::Image(_) => "<Image preview unsupported in this terminal>",
                 CachedPreview::LargeFile => "<File too large to preview>",
                 CachedPreview::NotFound => "<File not found>",
             },
@@ -186,6 +320,128 @@ impl<T, D> Injector<T, D> {
 }
 
 type ColumnFormatFn<T, D> = for<'a> fn(&'a T, &'a D) -> Cell<'a>;
+type ColumnSortKeyFn<T, D> = for<'a> fn(&'a T, &'a D) -> SortValue;
+
+/// A typed value to compare when ordering a table by a [`Column`], so that
+/// e.g. a "modified" column sorts chronologically rather than as strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortValue {
+    Text(String),
+    Number(f64),
+    Timestamp(i64),
+}
+
+impl SortValue {
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a.cmp(b),
+            (Self::Number(a), Self::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            (Self::Timestamp(a), Self::Timestamp(b)) => a.cmp(b),
+            // Mismatched kinds shouldn't happen for a single column, but don't panic if they do.
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// How a [`Column`]'s values should be interpreted when parsing a
+/// comparison or range predicate (`%size:>100`, `%modified:2024-01..2024-06`)
+/// written against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueKind {
+    /// Plain lexicographic comparison.
+    #[default]
+    Text,
+    /// A bare number, e.g. a line count.
+    Number,
+    /// A byte count, accepting human-friendly `K`/`M`/`G` suffixes.
+    Bytes,
+    /// A calendar date (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`), compared as a
+    /// Unix timestamp.
+    Date,
+}
+
+impl ValueKind {
+    /// Parses a comparison value written by the user into a [`SortValue`]
+    /// comparable with whatever a column's `sort_key` produces. Returns
+    /// `None` on a malformed value so the caller can fall back to treating
+    /// the token as a fuzzy pattern instead of erroring.
+    fn parse_value(self, text: &str) -> Option<SortValue> {
+        match self {
+            ValueKind::Text => Some(SortValue::Text(text.to_string())),
+            ValueKind::Number => text.parse().ok().map(SortValue::Number),
+            ValueKind::Bytes => parse_byte_size(text).map(SortValue::Number),
+            ValueKind::Date => parse_date(text).map(SortValue::Timestamp),
+        }
+    }
+}
+
+/// Parses a byte size with an optional `K`/`M`/`G` suffix (case-insensitive,
+/// binary multiples) into a plain byte count, e.g. `1.5M` -> `1572864.0`.
+fn parse_byte_size(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (number, multiplier) = match text.chars().last() {
+        Some('k' | 'K') => (&text[..text.len() - 1], 1024.0),
+        Some('m' | 'M') => (&text[..text.len() - 1], 1024.0 * 1024.0),
+        Some('g' | 'G') => (&text[..text.len() - 1], 1024.0 * 1024.0 * 1024.0),
+        _ => (text, 1.0),
+    };
+    number.trim().parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Parses a `YYYY`, `YYYY-MM` or `YYYY-MM-DD` date into a Unix timestamp
+/// (midnight UTC), using a leap-year-aware civil calendar calculation since
+/// pulling in a date/time crate for this would be overkill.
+fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = match parts.next() {
+        Some(m) => m.parse().ok()?,
+        None => 1,
+    };
+    let day: u32 = match parts.next() {
+        Some(d) => d.parse().ok()?,
+        None => 1,
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Howard Hinnant's days_from_civil algorithm.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i64;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days_since_epoch = era * 146097 + doe - 719468;
+    Some(days_since_epoch * 86400)
+}
+
+/// The direction a [`Column`] is currently sorted in, cycled by a user
+/// action: none -> ascending -> descending -> none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Ascending),
+            Some(Self::Ascending) => Some(Self::Descending),
+            Some(Self::Descending) => None,
+        }
+    }
+}
+
+/// Cached output of `display_order`, along with whether it's still valid.
+#[derive(Default)]
+struct OrderCache {
+    dirty: bool,
+    order: Vec<u32>,
+}
 
 pub struct Column<T, D> {
     name: String,
@@ -194,6 +450,13 @@ pub struct Column<T, D> {
     /// `DynamicPicker` uses this so that the dynamic column (for example regex in
     /// global search) is not used for filtering twice.
     filter: bool,
+    /// How to extract a typed, comparable value for this column, enabling
+    /// the user to order the table by it independent of match score. Also
+    /// used to evaluate `%column:>value`-style comparison predicates.
+    sort_key: Option<ColumnSortKeyFn<T, D>>,
+    /// How a comparison/range predicate's value should be parsed for this
+    /// column. Irrelevant without a `sort_key` to compare against.
+    value_kind: ValueKind,
 }
 
 impl<T, D> Column<T, D> {
@@ -202,6 +465,8 @@ impl<T, D> Column<T, D> {
             name: name.into(),
             format,
             filter: true,
+            sort_key: None,
+            value_kind: ValueKind::Text,
         }
     }
 
@@ -210,6 +475,16 @@ impl<T, D> Column<T, D> {
         self
     }
 
+    pub fn with_sort_key(mut self, sort_key: ColumnSortKeyFn<T, D>) -> Self {
+        self.sort_key = Some(sort_key);
+        self
+    }
+
+    pub fn with_value_kind(mut self, value_kind: ValueKind) -> Self {
+        self.value_kind = value_kind;
+        self
+    }
+
     fn format<'a>(&self, item: &'a T, data: &'a D) -> Cell<'a> {
         (self.format)(item, data)
     }
@@ -224,6 +499,9 @@ pub struct Picker<T: 'static + Send + Sync, D: 'static> {
     column_names: Arc<[Arc<str>]>,
     columns: Arc<[Column<T, D>]>,
     primary_column: usize,
+    /// The column `cycle_sort` targets, moved with `focus_sort_column`.
+    /// Defaults to `primary_column`.
+    sort_column: usize,
     editor_data: Arc<D>,
     version: Arc<AtomicUsize>,
     matcher: Nucleo<T>,
@@ -235,8 +513,39 @@ pub struct Picker<T: 'static + Send + Sync, D: 'static> {
     prompt: Prompt,
     query: query::PickerQuery,
 
+    /// Rows marked for a bulk action, keyed by their position in the current
+    /// nucleo snapshot. Cleared whenever the query changes, since a snapshot
+    /// index is only meaningful for the match set it was marked in.
+    marked: HashSet<u32>,
+
+    /// Frequency + recency scoring used to rank the default (empty-query)
+    /// order of items, blended with nucleo's match score otherwise.
+    frecency: FrecencyStore,
+
+    /// The column currently sorted by (by index into `columns`) and its
+    /// direction, if any. Takes priority over frecency/match-score ordering.
+    sort: Option<(usize, SortDirection)>,
+
+    /// Memoized result of `display_order`, recomputed only when the query,
+    /// sort or match set actually changed (`invalidate_order_cache`).
+    /// `display_order` is O(n log n) with a `sort_key`/frecency lookup per
+    /// comparison and runs on every render frame plus every cursor move, so
+    /// recomputing it unconditionally was a measurable per-frame cost on
+    /// large result sets.
+    order_cache: RefCell<OrderCache>,
+
+    /// Additional vertical offset applied on top of the preview's
+    /// match-centered position, so users can look at surrounding context.
+    /// Reset whenever the highlighted selection changes.
+    preview_scroll: isize,
+
     /// Whether to show the preview panel (default true)
     show_preview: bool,
+    /// Where the preview panel sits relative to the list (default horizontal).
+    layout: PickerLayout,
+    /// Percentage of the area given to the preview panel, 10-90 (see
+    /// `with_preview_ratio`; default 50).
+    preview_ratio: u8,
     /// Constraints for tabular formatting
     widths: Vec<Constraint>,
 
@@ -347,14 +656,25 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
             column_names,
             columns,
             primary_column: default_column,
+            sort_column: default_column,
             matcher,
             editor_data,
             version,
             cursor: 0,
             prompt,
             query: query::PickerQuery::default(),
+            marked: HashSet::new(),
+            frecency: FrecencyStore::default(),
+            sort: None,
+            order_cache: RefCell::new(OrderCache {
+                dirty: true,
+                order: Vec::new(),
+            }),
+            preview_scroll: 0,
             truncate_start: true,
             show_preview: true,
+            layout: PickerLayout::Horizontal,
+            preview_ratio: 50,
             callback_fn: Box::new(callback_fn),
             completion_height: 0,
             widths,
@@ -391,6 +711,19 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
         self
     }
 
+    /// Sets whether the preview pane sits beside the list or below it.
+    pub fn with_preview_layout(mut self, layout: PickerLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the percentage of the area given to the preview pane. Clamped to
+    /// a sane range so the list always keeps some usable space.
+    pub fn with_preview_ratio(mut self, ratio: u8) -> Self {
+        self.preview_ratio = ratio.clamp(10, 90);
+        self
+    }
+
     pub fn with_line(mut self, line: String, editor: &Editor) -> Self {
         self.prompt.set_line(line, editor);
         self.handle_prompt_change();
@@ -407,7 +740,7 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
 
     /// Move the cursor by a number of lines, either down (`Forward`) or up (`Backward`)
     pub fn move_by(&mut self, amount: u32, direction: Direction) {
-        let len = self.matcher.snapshot().matched_item_count();
+        let len = self.display_order(self.matcher.snapshot()).len() as u32;
 
         if len == 0 {
             // No results, can't move.
@@ -422,6 +755,7 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
                 self.cursor = self.cursor.saturating_add(len).saturating_sub(amount) % len;
             }
         }
+        self.preview_scroll = 0;
     }
 
     /// Move the cursor down by exactly one page. After the last page comes the first page.
@@ -437,31 +771,322 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
     /// Move the cursor to the first entry
     pub fn to_start(&mut self) {
         self.cursor = 0;
+        self.preview_scroll = 0;
     }
 
     /// Move the cursor to the last entry
     pub fn to_end(&mut self) {
         self.cursor = self
-            .matcher
-            .snapshot()
-            .matched_item_count()
-            .saturating_sub(1);
+            .display_order(self.matcher.snapshot())
+            .len()
+            .saturating_sub(1) as u32;
+        self.preview_scroll = 0;
+    }
+
+    /// Scrolls the preview pane by `amount` lines (negative scrolls up),
+    /// without moving the highlighted selection.
+    pub fn scroll_preview(&mut self, amount: isize) {
+        self.preview_scroll = self.preview_scroll.saturating_add(amount);
     }
 
     pub fn selection(&self) -> Option<&T> {
-        self.matcher
-            .snapshot()
-            .get_matched_item(self.cursor)
+        let snapshot = self.matcher.snapshot();
+        self.display_order(snapshot)
+            .get(self.cursor as usize)
+            .and_then(|&idx| snapshot.get_matched_item(idx))
+            .map(|item| item.data)
+    }
+
+    /// Marks the cached result of `display_order` stale, e.g. because the
+    /// query, sort column/direction, or match set changed.
+    fn invalidate_order_cache(&self) {
+        self.order_cache.borrow_mut().dirty = true;
+    }
+
+    /// The indices (into the current nucleo snapshot) of items that satisfy
+    /// every comparison/range predicate, in the order they should be
+    /// displayed: an explicit column sort takes priority; otherwise, when
+    /// the primary query is empty, matches are ranked by frecency -
+    /// recently/often confirmed items first; otherwise nucleo's own
+    /// match-score order is kept. Memoized in `order_cache` since this is
+    /// called on every render frame as well as every cursor move.
+    fn display_order(&self, snapshot: &nucleo::Snapshot<T>) -> Vec<u32> {
+        if !self.order_cache.borrow().dirty {
+            return self.order_cache.borrow().order.clone();
+        }
+
+        let count = snapshot.matched_item_count();
+        let mut order: Vec<u32> = (0..count)
+            .filter(|&idx| {
+                snapshot
+                    .get_matched_item(idx)
+                    .map(|item| self.passes_predicates(item.data))
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        if let Some((column, direction)) = self.sort {
+            // Compute each item's sort key once up front (a Schwartzian
+            // transform) rather than re-deriving it on every comparison.
+            let sort_key = self.columns[column].sort_key;
+            let mut keyed: Vec<(u32, Option<SortValue>)> = order
+                .into_iter()
+                .map(|idx| {
+                    let value = snapshot
+                        .get_matched_item(idx)
+                        .and_then(|item| sort_key.map(|key| key(item.data, &self.editor_data)));
+                    (idx, value)
+                })
+                .collect();
+            keyed.sort_by(|(_, a), (_, b)| match (a, b) {
+                (Some(a), Some(b)) => match direction {
+                    SortDirection::Ascending => a.compare(b),
+                    SortDirection::Descending => b.compare(a),
+                },
+                _ => std::cmp::Ordering::Equal,
+            });
+            order = keyed.into_iter().map(|(idx, _)| idx).collect();
+        } else if self.primary_query().is_empty() {
+            // Same idea: compute each item's frecency score (which
+            // allocates a `String` via `frecency_key`) once, not per
+            // comparison.
+            let mut keyed: Vec<(u32, f64)> = order
+                .into_iter()
+                .map(|idx| {
+                    let score = snapshot
+                        .get_matched_item(idx)
+                        .map(|item| self.frecency.score(&self.frecency_key(item.data)))
+                        .unwrap_or(0.0);
+                    (idx, score)
+                })
+                .collect();
+            keyed.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+            order = keyed.into_iter().map(|(idx, _)| idx).collect();
+        } else {
+            // A non-empty query keeps nucleo's own ranking, but blend in
+            // frecency as a tie-break: among items nucleo scores equally,
+            // the more recently/frequently opened one sorts first instead
+            // of falling back to arbitrary match order.
+            let pattern = self.primary_query();
+            let mut keyed: Vec<(u32, u32, f64)> = order
+                .into_iter()
+                .map(|idx| {
+                    let Some(item) = snapshot.get_matched_item(idx) else {
+                        return (idx, 0, 0.0);
+                    };
+                    let haystack = Utf32String::from(
+                        self.columns[self.primary_column]
+                            .format_text(item.data, &self.editor_data)
+                            .into_owned(),
+                    );
+                    let score = {
+                        let mut matcher = MATCHER.lock();
+                        Atom::new(
+                            &pattern,
+                            CaseMatching::Smart,
+                            Normalization::Smart,
+                            AtomKind::Fuzzy,
+                            false,
+                        )
+                        .score(haystack.slice(..), &mut matcher)
+                        .unwrap_or(0)
+                    };
+                    let frecency = self.frecency.score(&self.frecency_key(item.data));
+                    (idx, score, frecency)
+                })
+                .collect();
+            keyed.sort_by(|(_, score_a, frecency_a), (_, score_b, frecency_b)| {
+                score_b.cmp(score_a).then_with(|| {
+                    frecency_b
+                        .partial_cmp(frecency_a)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+            });
+            order = keyed.into_iter().map(|(idx, _, _)| idx).collect();
+        }
+
+        let mut cache = self.order_cache.borrow_mut();
+        cache.order = order.clone();
+        cache.dirty = false;
+        order
+    }
+
+    /// Toggles whether the currently highlighted row is marked for a bulk
+    /// action.
+    pub fn toggle_mark(&mut self) {
+        let snapshot = self.matcher.snapshot();
+        let Some(&idx) = self.display_order(snapshot).get(self.cursor as usize) else {
+            return;
+        };
+
+        if !self.marked.remove(&idx) {
+            self.marked.insert(idx);
+        }
+    }
+
+    /// Marks every row in the current match set.
+    pub fn mark_all(&mut self) {
+        self.marked = self
+            .display_order(self.matcher.snapshot())
+            .into_iter()
+            .collect();
+    }
+
+    fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// The items a confirm action should be applied to: every marked item,
+    /// or just the highlighted row when nothing is marked.
+    fn marked_or_selected(&self) -> Vec<&T> {
+        if self.marked.is_empty() {
+            return self.selection().into_iter().collect();
+        }
+
+        let snapshot = self.matcher.snapshot();
+        self.marked
+            .iter()
+            .filter_map(|&idx| snapshot.get_matched_item(idx))
             .map(|item| item.data)
+            .collect()
+    }
+
+    /// The frecency key for `item`: the text of its primary column.
+    fn frecency_key(&self, item: &T) -> String {
+        self.columns[self.primary_column]
+            .format_text(item, &self.editor_data)
+            .into_owned()
+    }
+
+    /// Records a frecency access for each of `items`. Called from every
+    /// confirm action - plain `Enter` and both splits - against the same
+    /// `marked_or_selected()` list the action is about to use, so frecency
+    /// ranking reflects how entries are actually opened rather than only the
+    /// plain-Enter path, without computing that list twice.
+    fn record_frecency_for_items(&mut self, items: &[&T]) {
+        let keys: Vec<String> = items.iter().map(|item| self.frecency_key(item)).collect();
+        for key in keys {
+            self.frecency.record_access(&key);
+        }
     }
 
     fn primary_query(&self) -> Arc<str> {
         self.query
             .get(&self.column_names[self.primary_column])
-            .cloned()
+            .map(|terms| query::pattern_text(terms).into())
             .unwrap_or_else(|| "".into())
     }
 
+    /// Whether `item` satisfies every query term that nucleo doesn't evaluate
+    /// itself: comparison/range predicates, alternations, and exact- or
+    /// contains-mode terms. Plain fuzzy (and negated-fuzzy) terms are already
+    /// accounted for by nucleo's own matched set and aren't re-checked here.
+    fn passes_predicates(&self, item: &T) -> bool {
+        self.query.iter().all(|(field, terms)| {
+            let Some(column) = self.columns.iter().find(|c| c.name.as_str() == &**field) else {
+                return true;
+            };
+            terms.iter().all(|term| self.passes_term(column, item, term))
+        })
+    }
+
+    fn passes_term(&self, column: &Column<T, D>, item: &T, term: &query::FieldQuery) -> bool {
+        use query::{CompareOp, MatchMode, Predicate};
+        use std::cmp::Ordering;
+
+        let pass = match &term.predicate {
+            // Fuzzy-ranked terms are handled by nucleo's own matched set;
+            // nothing to check here. Exact- and contains-mode terms are
+            // excluded from nucleo's pattern instead, so check them directly.
+            Predicate::Fuzzy(_) if term.mode == MatchMode::Fuzzy => return true,
+            Predicate::Fuzzy(text) if term.mode == MatchMode::Exact => {
+                column.format_text(item, &self.editor_data).as_ref() == text.as_ref()
+            }
+            Predicate::Fuzzy(text) => column
+                .format_text(item, &self.editor_data)
+                .to_lowercase()
+                .contains(&text.to_lowercase()),
+            // Not sent to nucleo (it has no OR combinator), so check it here:
+            // the row passes if any one alternative fuzzy-matches the
+            // column's text.
+            Predicate::Alternatives(patterns) => {
+                let haystack = Utf32String::from(
+                    column.format_text(item, &self.editor_data).into_owned(),
+                );
+                let mut matcher = MATCHER.lock();
+                patterns.iter().any(|pattern| {
+                    Atom::new(
+                        pattern,
+                        CaseMatching::Smart,
+                        Normalization::Smart,
+                        AtomKind::Fuzzy,
+                        false,
+                    )
+                    .score(haystack.slice(..), &mut matcher)
+                    .is_some()
+                })
+            }
+            Predicate::Compare(op, value) => {
+                // Columns without a sort key have no typed value to compare
+                // against: fall back to the same plain-substring check used
+                // for a malformed comparison value below, rather than
+                // treating the term as an unconditional pass.
+                match column
+                    .sort_key
+                    .map(|key| key(item, &self.editor_data))
+                    .zip(column.value_kind.parse_value(value))
+                {
+                    Some((item_value, target)) => {
+                        let ordering = item_value.compare(&target);
+                        match op {
+                            CompareOp::Gt => ordering == Ordering::Greater,
+                            CompareOp::Ge => ordering != Ordering::Less,
+                            CompareOp::Lt => ordering == Ordering::Less,
+                            CompareOp::Le => ordering != Ordering::Greater,
+                            CompareOp::Eq => ordering == Ordering::Equal,
+                        }
+                    }
+                    // No sort key, or a malformed comparison value: fall back
+                    // to matching the whole token as a plain substring
+                    // instead of erroring.
+                    None => column
+                        .format_text(item, &self.editor_data)
+                        .to_lowercase()
+                        .contains(&value.to_lowercase()),
+                }
+            }
+            Predicate::Range(lo, hi) => {
+                match column
+                    .sort_key
+                    .map(|key| key(item, &self.editor_data))
+                    .zip(
+                        column
+                            .value_kind
+                            .parse_value(lo)
+                            .zip(column.value_kind.parse_value(hi)),
+                    ) {
+                    Some((item_value, (lo_value, hi_value))) => {
+                        item_value.compare(&lo_value) != Ordering::Less
+                            && item_value.compare(&hi_value) != Ordering::Greater
+                    }
+                    // No sort key, or a malformed range bound: fall back to
+                    // matching the whole token as a plain substring instead
+                    // of erroring.
+                    None => column
+                        .format_text(item, &self.editor_data)
+                        .to_lowercase()
+                        .contains(&format!("{lo}..{hi}").to_lowercase()),
+                }
+            }
+        };
+
+        if term.negate {
+            !pass
+        } else {
+            pass
+        }
+    }
+
     fn header_height(&self) -> u16 {
         if self.columns.len() > 1 {
             1
@@ -474,6 +1099,56 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
         self.show_preview = !self.show_preview;
     }
 
+    /// Moves the column that `cycle_sort` targets to the next (or, going
+    /// `Backward`, previous) column that has a `sort_key`, wrapping around.
+    /// A no-op if no column is sortable.
+    pub fn focus_sort_column(&mut self, direction: Direction) {
+        let sortable: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| column.sort_key.is_some())
+            .map(|(i, _)| i)
+            .collect();
+        if sortable.is_empty() {
+            return;
+        }
+
+        let len = sortable.len();
+        let pos = sortable.iter().position(|&col| col == self.sort_column);
+        let next = match (pos, direction) {
+            (Some(pos), Direction::Forward) => (pos + 1) % len,
+            (Some(pos), Direction::Backward) => (pos + len - 1) % len,
+            // Not on a sortable column yet (e.g. an unsortable primary
+            // column): step to the nearest sortable column in `direction`.
+            (None, Direction::Forward) => sortable
+                .iter()
+                .position(|&col| col > self.sort_column)
+                .unwrap_or(0),
+            (None, Direction::Backward) => sortable
+                .iter()
+                .rposition(|&col| col < self.sort_column)
+                .unwrap_or(len - 1),
+        };
+        self.sort_column = sortable[next];
+    }
+
+    /// Cycles the sort direction of the column focused via
+    /// `focus_sort_column` (the primary column by default): none ->
+    /// ascending -> descending -> none. Falls back to match-score/frecency
+    /// ordering when the column has no sort key or the cycle returns to
+    /// `None`.
+    pub fn cycle_sort(&mut self) {
+        let column = self.sort_column;
+        if self.columns[column].sort_key.is_none() {
+            return;
+        }
+
+        let current = self.sort.filter(|(col, _)| *col == column).map(|(_, d)| d);
+        self.sort = SortDirection::next(current).map(|direction| (column, direction));
+        self.invalidate_order_cache();
+    }
+
     fn prompt_handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
         if let EventResult::Consumed(_) = self.prompt.handle_event(event, cx) {
             self.handle_prompt_change();
@@ -492,25 +1167,28 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
                 .filter(|column| column.filter)
                 .enumerate()
             {
-                let pattern: &str = new_query
+                let pattern = new_query
                     .get(column.name.as_str())
-                    .map(|f| &**f)
-                    .unwrap_or("");
+                    .map(|terms| query::pattern_text(terms))
+                    .unwrap_or_default();
                 let append = self
                     .query
                     .get(column.name.as_str())
-                    .map(|old_pattern| pattern.starts_with(&**old_pattern))
+                    .map(|old_terms| pattern.starts_with(&query::pattern_text(old_terms)))
                     .unwrap_or(false);
 
                 self.matcher.pattern.reparse(
                     i,
-                    pattern,
+                    &pattern,
                     CaseMatching::Smart,
                     Normalization::Smart,
                     append,
                 );
             }
             self.query = new_query;
+            self.clear_marks();
+            self.invalidate_order_cache();
+            self.preview_scroll = 0;
         }
     }
 
@@ -553,16 +1231,29 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
                     // Read up to 1kb to detect the content type
                     let n = file.take(1024).read_to_end(&mut self.read_buffer)?;
                     let content_type = content_inspector::inspect(&self.read_buffer[..n]);
+                    // Keep a copy of what we read so a binary file can reuse it for a
+                    // hex dump / image-magic-bytes check below, then clear the scratch
+                    // buffer for the next preview.
+                    let head = self.read_buffer[..n].to_vec();
                     self.read_buffer.clear();
-                    Ok((metadata, content_type))
+                    Ok((metadata, content_type, head))
                 });
                 let preview = data
                     .map(
-                        |(metadata, content_type)| match (metadata.len(), content_type) {
-                            (_, content_inspector::ContentType::BINARY) => CachedPreview::Binary,
+                        |(metadata, content_type, head)| match (metadata.len(), content_type) {
                             (size, _) if size > MAX_FILE_SIZE_FOR_PREVIEW => {
                                 CachedPreview::LargeFile
                             }
+                            // Below the size cap, so this read is bounded just
+                            // like every other preview path.
+                            (_, content_inspector::ContentType::BINARY) if is_image(&head) => {
+                                std::fs::read(&path)
+                                    .map(CachedPreview::Image)
+                                    .unwrap_or(CachedPreview::Binary)
+                            }
+                            (_, content_inspector::ContentType::BINARY) => {
+                                CachedPreview::HexDump(hex_dump(&head))
+                            }
                             _ => Document::open(&path, None, None, editor.config.clone())
                                 .map(|doc| {
                                     // Asynchronously highlight the new document
@@ -588,11 +1279,13 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
 
     fn render_picker(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
         let status = self.matcher.tick(10);
+        if status.changed {
+            self.invalidate_order_cache();
+        }
         let snapshot = self.matcher.snapshot();
+        let order = self.display_order(snapshot);
         if status.changed {
-            self.cursor = self
-                .cursor
-                .min(snapshot.matched_item_count().saturating_sub(1))
+            self.cursor = self.cursor.min(order.len().saturating_sub(1) as u32)
         }
 
         let text_style = cx.editor.theme.get("ui.text");
@@ -621,7 +1314,7 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
         let count = format!(
             "{}{}/{}",
             if status.running { "(running) " } else { "" },
-            snapshot.matched_item_count(),
+            order.len(),
             snapshot.item_count(),
         );
         surface.set_stringn(
@@ -647,9 +1340,7 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
         let rows = inner.height.saturating_sub(self.header_height()) as u32;
         let offset = self.cursor - (self.cursor % std::cmp::max(1, rows));
         let cursor = self.cursor.saturating_sub(offset);
-        let end = offset
-            .saturating_add(rows)
-            .min(snapshot.matched_item_count());
+        let end = offset.saturating_add(rows).min(order.len() as u32);
         let mut indices = Vec::new();
         let mut matcher = MATCHER.lock();
         matcher.config = Config::DEFAULT;
@@ -657,11 +1348,17 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
             matcher.config.set_match_paths()
         }
 
-        let options = snapshot.matched_items(offset..end).map(|item| {
+        let marked = &self.marked;
+        let options = (offset..end).filter_map(|i| {
+            let item_index = order[i as usize];
+            snapshot.get_matched_item(item_index).map(|item| (item_index, item))
+        }).map(|(item_index, item)| {
+            let mark = if marked.contains(&item_index) { " ● " } else { "   " };
+
             let mut widths = self.widths.iter_mut();
             let mut matcher_index = 0;
 
-            Row::new(self.columns.iter().map(|column| {
+            let cells = std::iter::once(Cell::from(mark)).chain(self.columns.iter().map(|column| {
                 let Some(Constraint::Length(max_width)) = widths.next() else {
                     unreachable!();
                 };
@@ -727,24 +1424,37 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
                 }
 
                 cell
-            }))
+            }));
+
+            Row::new(cells)
         });
 
+        let mut widths = Vec::with_capacity(self.widths.len() + 1);
+        widths.push(Constraint::Length(3));
+        widths.extend(self.widths.iter().cloned());
+
         let mut table = Table::new(options)
             .style(text_style)
             .highlight_style(selected)
             .highlight_symbol(" > ")
             .column_spacing(1)
-            .widths(&self.widths);
+            .widths(&widths);
 
         // -- Header
         if self.columns.len() > 1 {
             let header_style = cx.editor.theme.get("ui.picker.header");
 
-            table =
-                table.header(Row::new(self.columns.iter().map(|column| {
-                    Cell::from(Span::styled(column.name.as_str(), header_style))
-                })));
+            let header_cells = std::iter::once(Cell::from("")).chain(self.columns.iter().enumerate().map(
+                |(i, column)| {
+                    let arrow = match self.sort {
+                        Some((col, SortDirection::Ascending)) if col == i => " ▲",
+                        Some((col, SortDirection::Descending)) if col == i => " ▼",
+                        _ => "",
+                    };
+                    Cell::from(Span::styled(format!("{}{arrow}", column.name), header_style))
+                },
+            ));
+            table = table.header(Row::new(header_cells));
         }
 
         use tui::widgets::TableState;
@@ -779,6 +1489,33 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
 
         if let Some((path, range)) = self.current_file(cx.editor) {
             let preview = self.get_preview(path, cx.editor);
+
+            if let Preview::Cached(CachedPreview::HexDump(dump)) = preview {
+                let lines: Vec<&str> = dump.lines().collect();
+                let max_scroll = lines.len().saturating_sub(inner.height as usize);
+                let scroll = self
+                    .preview_scroll
+                    .max(0)
+                    .min(max_scroll as isize) as usize;
+                for (i, line) in lines
+                    .iter()
+                    .skip(scroll)
+                    .take(inner.height as usize)
+                    .enumerate()
+                {
+                    surface.set_stringn(inner.x, inner.y + i as u16, line, inner.width as usize, text);
+                }
+                return;
+            }
+
+            if let Preview::Cached(CachedPreview::Image(bytes)) = preview {
+                if kitty_graphics_supported() && is_png(bytes) {
+                    let escape = kitty_image_escape(bytes, inner.width, inner.height);
+                    surface.set_string(inner.x, inner.y, escape, text);
+                    return;
+                }
+            }
+
             let doc = match preview.document() {
                 Some(doc)
                     if range.map_or(true, |(start, end)| {
@@ -822,6 +1559,17 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
                     offset.anchor = start;
                 }
             }
+            if self.preview_scroll != 0 {
+                let text_fmt = doc.text_format(inner.width, None);
+                (offset.anchor, offset.vertical_offset) = char_idx_at_visual_offset(
+                    doc.text().slice(..),
+                    offset.anchor,
+                    offset.vertical_offset as isize + self.preview_scroll,
+                    0,
+                    &text_fmt,
+                    &TextAnnotations::default(),
+                );
+            }
 
             let syntax_highlights = EditorView::doc_syntax_highlights(
                 doc,
@@ -879,34 +1627,52 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
 
 impl<I: 'static + Send + Sync, D: 'static + Send + Sync> Component for Picker<I, D> {
     fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
-        // +---------+ +---------+
-        // |prompt   | |preview  |
-        // +---------+ |         |
-        // |picker   | |         |
-        // |         | |         |
-        // +---------+ +---------+
-
-        let render_preview =
-            self.show_preview && self.file_fn.is_some() && area.width > MIN_AREA_WIDTH_FOR_PREVIEW;
-
-        let picker_width = if render_preview {
-            area.width / 2
+        // Horizontal layout (default):    Vertical layout:
+        // +---------+ +---------+         +---------------+
+        // |prompt   | |preview  |          |prompt         |
+        // +---------+ |         |          +---------------+
+        // |picker   | |         |          |picker         |
+        // |         | |         |          +---------------+
+        // +---------+ +---------+          |preview        |
+        //                                  +---------------+
+
+        let area_fits_preview = match self.layout {
+            PickerLayout::Horizontal => area.width > MIN_AREA_WIDTH_FOR_PREVIEW,
+            PickerLayout::Vertical => area.height > MIN_AREA_HEIGHT_FOR_PREVIEW,
+        };
+        let render_preview = self.show_preview && self.file_fn.is_some() && area_fits_preview;
+
+        let (picker_area, preview_area) = if !render_preview {
+            (area, None)
         } else {
-            area.width
+            match self.layout {
+                PickerLayout::Horizontal => {
+                    let preview_width =
+                        (area.width as u32 * self.preview_ratio as u32 / 100) as u16;
+                    let picker_width = area.width - preview_width;
+                    let picker_area = area.with_width(picker_width);
+                    let preview_area = area.clip_left(picker_width);
+                    (picker_area, Some(preview_area))
+                }
+                PickerLayout::Vertical => {
+                    let preview_height =
+                        (area.height as u32 * self.preview_ratio as u32 / 100) as u16;
+                    let picker_height = area.height - preview_height;
+                    let picker_area = area.with_height(picker_height);
+                    let preview_area = area.clip_top(picker_height);
+                    (picker_area, Some(preview_area))
+                }
+            }
         };
 
-        let picker_area = area.with_width(picker_width);
         self.render_picker(picker_area, surface, cx);
 
-        if render_preview {
-            let preview_area = area.clip_left(picker_width);
+        if let Some(preview_area) = preview_area {
             self.render_preview(preview_area, surface, cx);
         }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut Context) -> EventResult {
-        // TODO: keybinds for scrolling preview
-
         let key_event = match event {
             Event::Key(event) => *event,
             Event::Paste(..) => return self.prompt_handle_event(event, ctx),
@@ -963,19 +1729,31 @@ impl<I: 'static + Send + Sync, D: 'static + Send + Sync> Component for Picker<I,
                 }
             }
             key!(Enter) => {
-                if let Some(option) = self.selection() {
+                let options = self.marked_or_selected();
+                self.record_frecency_for_items(&options);
+                for option in options {
                     (self.callback_fn)(ctx, option, Action::Replace);
                 }
                 return close_fn(self);
             }
+            ctrl!('q') => {
+                self.toggle_mark();
+            }
+            alt!('a') => {
+                self.mark_all();
+            }
             ctrl!('s') => {
-                if let Some(option) = self.selection() {
+                let options = self.marked_or_selected();
+                self.record_frecency_for_items(&options);
+                for option in options {
                     (self.callback_fn)(ctx, option, Action::HorizontalSplit);
                 }
                 return close_fn(self);
             }
             ctrl!('v') => {
-                if let Some(option) = self.selection() {
+                let options = self.marked_or_selected();
+                self.record_frecency_for_items(&options);
+                for option in options {
                     (self.callback_fn)(ctx, option, Action::VerticalSplit);
                 }
                 return close_fn(self);
@@ -983,6 +1761,18 @@ impl<I: 'static + Send + Sync, D: 'static + Send + Sync> Component for Picker<I,
             ctrl!('t') => {
                 self.toggle_preview();
             }
+            alt!('s') => {
+                self.cycle_sort();
+            }
+            alt!('S') => {
+                self.focus_sort_column(Direction::Forward);
+            }
+            alt!(Down) => {
+                self.scroll_preview(1);
+            }
+            alt!(Up) => {
+                self.scroll_preview(-1);
+            }
             _ => {
                 self.prompt_handle_event(event, ctx);
             }
@@ -1031,6 +1821,11 @@ pub struct DynamicPicker<T: 'static + Send + Sync, D: 'static + Send + Sync> {
     file_picker: Picker<T, D>,
     query_callback: DynQueryCallback<T>,
     query: String,
+    /// Bumped every time a new query is dispatched. Carried into the
+    /// spawned job so that a result which resolves after a newer query was
+    /// dispatched can recognize itself as stale and discard its results
+    /// instead of clobbering the current ones.
+    query_generation: Arc<AtomicUsize>,
 }
 
 impl<T: Send + Sync, D: Send + Sync> DynamicPicker<T, D> {
@@ -1039,6 +1834,7 @@ impl<T: Send + Sync, D: Send + Sync> DynamicPicker<T, D> {
             file_picker,
             query_callback,
             query: String::new(),
+            query_generation: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
@@ -1050,9 +1846,7 @@ impl<T: Send + Sync + 'static, D: Send + Sync + 'static> Component for DynamicPi
 
     fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
         let event_result = self.file_picker.handle_event(event, cx);
-        let Some(current_query) = self.file_picker.primary_query() else {
-            return event_result;
-        };
+        let current_query = self.file_picker.primary_query();
 
         if !matches!(event, Event::IdleTimeout) || self.query == *current_query {
             return event_result;
@@ -1060,11 +1854,18 @@ impl<T: Send + Sync + 'static, D: Send + Sync + 'static> Component for DynamicPi
 
         self.query = current_query.to_string();
 
+        let generation = self.query_generation.fetch_add(1, atomic::Ordering::Relaxed) + 1;
+        let query_generation = self.query_generation.clone();
         let new_options = (self.query_callback)(current_query.to_owned(), cx.editor);
 
         cx.jobs.callback(async move {
             let new_options = new_options.await?;
             let callback = Callback::EditorCompositor(Box::new(move |_editor, compositor| {
+                if query_generation.load(atomic::Ordering::Relaxed) != generation {
+                    // A newer query has since been dispatched; this result is stale.
+                    return;
+                }
+
                 // Wrapping of pickers in overlay is done outside the picker code,
                 // so this is fragile and will break if wrapped in some other widget.
                 let picker = match compositor.find_id::<Overlay<Self>>(ID) {
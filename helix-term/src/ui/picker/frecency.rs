@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const HOUR_SECS: u64 = 60 * 60;
+const DAY_SECS: u64 = 24 * HOUR_SECS;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+
+/// Visits older than this contribute nothing beyond the floor decay weight,
+/// so there's no point keeping them around for the rest of the session.
+const MAX_VISIT_AGE_SECS: u64 = 4 * WEEK_SECS;
+/// Caps how many timestamps we keep per key, oldest first, so a key that's
+/// confirmed repeatedly within `MAX_VISIT_AGE_SECS` can't grow its entry
+/// unboundedly over a long-lived session.
+const MAX_VISITS_PER_KEY: usize = 32;
+
+/// Tracks how often and how recently picker items have been confirmed so
+/// that, when the query is empty (or match scores tie), recently/frequently
+/// used entries can be surfaced first - similar to a shell's `z`/autojump.
+#[derive(Default)]
+pub(super) struct FrecencyStore {
+    /// Timestamps (unix seconds) of recorded accesses, keyed by the item's
+    /// primary-column text. Old and excess entries are dropped by `prune`.
+    visits: HashMap<String, Vec<u64>>,
+}
+
+impl FrecencyStore {
+    pub fn record_access(&mut self, key: &str) {
+        let now = now_secs();
+        let visits = self.visits.entry(key.to_string()).or_default();
+        visits.push(now);
+        prune(visits, now);
+    }
+
+    /// Combined frequency + recency score for `key`: each visit contributes
+    /// a weight that decays in half-life-ish buckets, so an item accessed
+    /// many times an hour ago still outranks one touched once last month.
+    pub fn score(&self, key: &str) -> f64 {
+        let Some(visits) = self.visits.get(key) else {
+            return 0.0;
+        };
+
+        let now = now_secs();
+        visits
+            .iter()
+            .map(|&accessed_at| decay_weight(now.saturating_sub(accessed_at)))
+            .sum()
+    }
+}
+
+/// The decay weight for a single visit `age` seconds old. Split out of
+/// `score` so the bucket boundaries can be unit-tested without mocking the
+/// system clock.
+fn decay_weight(age: u64) -> f64 {
+    match age {
+        a if a <= HOUR_SECS => 4.0,
+        a if a <= DAY_SECS => 2.0,
+        a if a <= WEEK_SECS => 1.0,
+        _ => 0.25,
+    }
+}
+
+/// Drops visits older than `MAX_VISIT_AGE_SECS` (they've already decayed to
+/// the score floor, so keeping them serves no purpose) and, if that still
+/// leaves more than `MAX_VISITS_PER_KEY`, drops the oldest excess.
+fn prune(visits: &mut Vec<u64>, now: u64) {
+    visits.retain(|&accessed_at| now.saturating_sub(accessed_at) <= MAX_VISIT_AGE_SECS);
+    if visits.len() > MAX_VISITS_PER_KEY {
+        let excess = visits.len() - MAX_VISITS_PER_KEY;
+        visits.drain(..excess);
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decay_weight_bucket_boundaries_test() {
+        // Each boundary is inclusive of the closer (higher-weight) bucket.
+        assert_eq!(decay_weight(0), 4.0);
+        assert_eq!(decay_weight(HOUR_SECS), 4.0);
+        assert_eq!(decay_weight(HOUR_SECS + 1), 2.0);
+
+        assert_eq!(decay_weight(DAY_SECS), 2.0);
+        assert_eq!(decay_weight(DAY_SECS + 1), 1.0);
+
+        assert_eq!(decay_weight(WEEK_SECS), 1.0);
+        assert_eq!(decay_weight(WEEK_SECS + 1), 0.25);
+    }
+
+    #[test]
+    fn prune_drops_stale_visits_test() {
+        let now = 10 * WEEK_SECS;
+        let mut visits = vec![0, now - MAX_VISIT_AGE_SECS - 1, now - HOUR_SECS, now];
+
+        prune(&mut visits, now);
+
+        assert_eq!(visits, vec![now - HOUR_SECS, now]);
+    }
+
+    #[test]
+    fn prune_caps_visits_per_key_test() {
+        let now = WEEK_SECS;
+        let mut visits: Vec<u64> = (0..MAX_VISITS_PER_KEY as u64 + 5)
+            .map(|i| now - i)
+            .rev()
+            .collect();
+
+        prune(&mut visits, now);
+
+        assert_eq!(visits.len(), MAX_VISITS_PER_KEY);
+        // The oldest entries (smallest timestamps) are the ones dropped.
+        assert_eq!(*visits.first().unwrap(), now - MAX_VISITS_PER_KEY as u64 + 1);
+    }
+
+    #[test]
+    fn record_access_caps_visits_per_key_test() {
+        let mut store = FrecencyStore::default();
+        for _ in 0..MAX_VISITS_PER_KEY + 5 {
+            store.record_access("a");
+        }
+
+        assert_eq!(store.visits["a"].len(), MAX_VISITS_PER_KEY);
+    }
+}
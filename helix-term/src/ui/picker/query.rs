@@ -1,27 +1,160 @@
 use std::{collections::HashMap, sync::Arc};
 
-pub(super) type PickerQuery = HashMap<Arc<str>, Arc<str>>;
+/// A comparison operator recognized in a field value, e.g. the `>` in
+/// `%size:>100`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+/// What a field's raw text resolves to once `parse` has looked at its shape.
+/// Only `Fuzzy` terms are handed to nucleo; the rest are evaluated separately
+/// since nucleo has no notion of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Predicate {
+    Fuzzy(Arc<str>),
+    /// Several fuzzy patterns OR-combined (`%ext:rs|toml|md`): a row is kept
+    /// if it matches any one of them.
+    Alternatives(Vec<Arc<str>>),
+    Compare(CompareOp, Arc<str>),
+    /// An inclusive range, e.g. `2024-01..2024-06`.
+    Range(Arc<str>, Arc<str>),
+}
+
+/// Whether a field's `Fuzzy` predicate should be fuzzy-ranked by nucleo (the
+/// default), checked for exact equality by using `=` rather than `:` as the
+/// field separator (`%name=exact-value`), or checked for plain substring
+/// (non-fuzzy) containment via a leading `~` sigil on the value
+/// (`%name:~substring`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum MatchMode {
+    #[default]
+    Fuzzy,
+    Exact,
+    Contains,
+}
+
+/// A single parsed term within a field's query: what to match, and whether
+/// it should exclude rather than select matching entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FieldQuery {
+    pub predicate: Predicate,
+    /// When set, entries whose column matches `predicate` are hidden instead
+    /// of ranked, mirroring the negative-filter facets search engines expose.
+    pub negate: bool,
+    /// Only meaningful for `Predicate::Fuzzy`; see [`MatchMode`].
+    pub mode: MatchMode,
+}
+
+pub(super) type PickerQuery = HashMap<Arc<str>, Vec<FieldQuery>>;
+
+/// Classifies a field's raw (unescaped) text into a predicate: a leading
+/// comparison operator or an infix `..` produces a structured predicate,
+/// otherwise the text is a plain fuzzy pattern.
+fn classify(raw: String) -> Predicate {
+    if let Some(rest) = raw.strip_prefix(">=") {
+        return Predicate::Compare(CompareOp::Ge, rest.into());
+    }
+    if let Some(rest) = raw.strip_prefix("<=") {
+        return Predicate::Compare(CompareOp::Le, rest.into());
+    }
+    if let Some(rest) = raw.strip_prefix('>') {
+        return Predicate::Compare(CompareOp::Gt, rest.into());
+    }
+    if let Some(rest) = raw.strip_prefix('<') {
+        return Predicate::Compare(CompareOp::Lt, rest.into());
+    }
+    if let Some(rest) = raw.strip_prefix('=') {
+        return Predicate::Compare(CompareOp::Eq, rest.into());
+    }
+    if let Some((start, end)) = raw.split_once("..") {
+        if !start.is_empty() && !end.is_empty() {
+            return Predicate::Range(start.into(), end.into());
+        }
+    }
+    Predicate::Fuzzy(raw.into())
+}
+
+/// Reassembles a field's fuzzy terms into the single pattern string nucleo's
+/// own matcher expects, re-attaching nucleo's `!` negation prefix to negated
+/// terms so its matcher inverts the keep/score decision for them.
+/// `Alternatives`/`Compare`/`Range` terms are skipped; those are evaluated
+/// separately since nucleo has no notion of OR-combination, numeric or date
+/// comparisons.
+pub(super) fn pattern_text(terms: &[FieldQuery]) -> String {
+    let mut pattern = String::new();
+    for term in terms {
+        let Predicate::Fuzzy(text) = &term.predicate else {
+            continue;
+        };
+        // Exact- and contains-mode terms are checked directly against the
+        // column's text instead of being ranked by nucleo.
+        if term.mode != MatchMode::Fuzzy {
+            continue;
+        }
+        if !pattern.is_empty() {
+            pattern.push(' ');
+        }
+        if term.negate {
+            pattern.push('!');
+        }
+        pattern.push_str(text);
+    }
+    pattern
+}
 
 pub(super) fn parse(column_names: &[Arc<str>], primary_column: usize, input: &str) -> PickerQuery {
-    let mut fields: HashMap<Arc<str>, String> = HashMap::new();
+    let mut fields: PickerQuery = HashMap::new();
     let primary_field = &column_names[primary_column];
     let mut escaped = false;
     let mut quoted = false;
     let mut in_field = false;
+    let mut negate = false;
+    let mut mode = MatchMode::Fuzzy;
     let mut field = None;
     let mut text = String::new();
+    // Alternatives accumulated so far for the field value currently being
+    // scanned, split off of `text` by an unquoted, unescaped '|'.
+    let mut alternatives: Vec<String> = Vec::new();
 
     macro_rules! finish_field {
         () => {
+            // Whether this term was scoped to an explicit `%field:`/`%field=`.
+            // Comparison/range/alternation operators only apply there: on the
+            // primary/unnamed column a query is unstructured text (often a
+            // regex for live-grep), so `>`, `..`, `|` etc. must stay literal.
+            let scoped = field.is_some();
             let key = field.take().unwrap_or(primary_field);
-
-            if let Some(pattern) = fields.get_mut(key) {
-                pattern.push(' ');
-                pattern.push_str(&text);
-                text.clear();
+            let raw_pattern = std::mem::take(&mut text);
+            let mut alts = std::mem::take(&mut alternatives);
+            // A bare `!` has nothing to negate, so treat it as the literal
+            // pattern "!" rather than an empty negated term.
+            let bare_negation = alts.is_empty() && negate && raw_pattern.is_empty();
+            let predicate = if bare_negation {
+                Predicate::Fuzzy("!".into())
+            } else if !scoped {
+                Predicate::Fuzzy(raw_pattern.into())
+            } else if alts.is_empty() {
+                classify(raw_pattern)
             } else {
-                fields.insert(key.clone(), std::mem::take(&mut text));
-            }
+                alts.push(raw_pattern);
+                Predicate::Alternatives(alts.into_iter().map(Into::into).collect())
+            };
+            let negate_flag = !bare_negation && negate;
+            negate = false;
+            let mode_flag = std::mem::take(&mut mode);
+            fields
+                .entry(key.clone())
+                .or_insert_with(Vec::new)
+                .push(FieldQuery {
+                    predicate,
+                    negate: negate_flag,
+                    mode: mode_flag,
+                });
         };
     }
 
@@ -30,8 +163,8 @@ pub(super) fn parse(column_names: &[Arc<str>], primary_column: usize, input: &st
             // Backslash escaping
             '\\' => escaped = !escaped,
             _ if escaped => {
-                // Allow escaping '%' and '"'
-                if !matches!(ch, '%' | '"') {
+                // Allow escaping '%', '"', '!', '|', '=' and '~'
+                if !matches!(ch, '%' | '"' | '!' | '|' | '=' | '~') {
                     text.push('\\');
                 }
                 text.push(ch);
@@ -39,15 +172,17 @@ pub(super) fn parse(column_names: &[Arc<str>], primary_column: usize, input: &st
             }
             // Double quoting
             '"' => quoted = !quoted,
-            '%' | ':' | ' ' if quoted => text.push(ch),
+            '%' | ':' | ' ' | '|' | '=' if quoted => text.push(ch),
             // Space either completes the current word if no field is specified
             // or field if one is specified.
-            '%' | ' ' if !text.is_empty() => {
+            '%' | ' ' if !text.is_empty() || negate || !alternatives.is_empty() => {
                 finish_field!();
                 in_field = ch == '%';
             }
             '%' => in_field = true,
-            ':' if in_field => {
+            // `=` is an alternative field separator to `:` that additionally
+            // requests exact (non-fuzzy) matching, e.g. `%name=exact-value`.
+            ':' | '=' if in_field => {
                 // Go over all columns and their indices, find all that starts with field key,
                 // select a column that fits key the most.
                 field = column_names
@@ -55,29 +190,70 @@ pub(super) fn parse(column_names: &[Arc<str>], primary_column: usize, input: &st
                     .filter(|col| col.starts_with(&text))
                     // select "fittest" column
                     .min_by_key(|col| col.len());
+                if ch == '=' {
+                    mode = MatchMode::Exact;
+                }
                 text.clear();
                 in_field = false;
             }
+            // A leading, unquoted '!' negates the term that follows it.
+            '!' if text.is_empty() && !in_field && !negate => negate = true,
+            // A leading, unquoted '~' on a scoped field's value requests
+            // plain substring (non-fuzzy) matching instead of nucleo
+            // ranking, e.g. `%name:~substring`.
+            '~' if text.is_empty()
+                && !in_field
+                && field.is_some()
+                && mode == MatchMode::Fuzzy =>
+            {
+                mode = MatchMode::Contains;
+            }
+            // An unquoted '|' separates alternatives within a single named
+            // field's value, e.g. `%ext:rs|toml|md`. On the primary/unnamed
+            // column it's kept literal (e.g. a live-grep regex alternation).
+            '|' if !in_field && field.is_some() => alternatives.push(std::mem::take(&mut text)),
             _ => text.push(ch),
         }
     }
 
-    if !in_field && !text.is_empty() {
+    if !in_field && (!text.is_empty() || negate || !alternatives.is_empty()) {
         finish_field!();
     }
 
     fields
-        .into_iter()
-        .map(|(field, query)| (field, query.as_str().into()))
-        .collect()
 }
 
 #[cfg(test)]
 mod test {
-    use helix_core::hashmap;
-
     use super::*;
 
+    fn term(pattern: &str) -> Vec<FieldQuery> {
+        vec![FieldQuery {
+            predicate: Predicate::Fuzzy(pattern.into()),
+            negate: false,
+            mode: MatchMode::Fuzzy,
+        }]
+    }
+
+    fn terms(patterns: &[&str]) -> Vec<FieldQuery> {
+        patterns
+            .iter()
+            .map(|pattern| FieldQuery {
+                predicate: Predicate::Fuzzy((*pattern).into()),
+                negate: false,
+                mode: MatchMode::Fuzzy,
+            })
+            .collect()
+    }
+
+    fn negated(pattern: &str) -> Vec<FieldQuery> {
+        vec![FieldQuery {
+            predicate: Predicate::Fuzzy(pattern.into()),
+            negate: true,
+            mode: MatchMode::Fuzzy,
+        }]
+    }
+
     #[test]
     fn parse_query_test() {
         let columns = &[
@@ -92,119 +268,379 @@ mod test {
         // Basic field splitting
         assert_eq!(
             parse(columns, primary_column, "hello world"),
-            hashmap!(
-                "primary".into() => "hello world".into(),
-            )
+            HashMap::from([("primary".into(), terms(&["hello", "world"]))])
         );
         assert_eq!(
             parse(columns, primary_column, "hello %field1:world %field2:!"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "field1".into() => "world".into(),
-                "field2".into() => "!".into(),
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("field1".into(), term("world")),
+                ("field2".into(), term("!")),
+            ])
         );
         assert_eq!(
             parse(columns, primary_column, "%field1:abc %field2:def xyz"),
-            hashmap!(
-                "primary".into() => "xyz".into(),
-                "field1".into() => "abc".into(),
-                "field2".into() => "def".into(),
-            )
+            HashMap::from([
+                ("primary".into(), term("xyz")),
+                ("field1".into(), term("abc")),
+                ("field2".into(), term("def")),
+            ])
         );
 
         // Trailing space is trimmed
         assert_eq!(
             parse(columns, primary_column, "hello "),
-            hashmap!(
-                "primary".into() => "hello".into(),
-            )
+            HashMap::from([("primary".into(), term("hello"))])
         );
 
         // Trailing fields are trimmed.
         assert_eq!(
             parse(columns, primary_column, "hello %foo"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-            )
+            HashMap::from([("primary".into(), term("hello"))])
         );
 
         // Quoting
         assert_eq!(
             parse(columns, primary_column, r#"hello %field1:"a b c""#),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "field1".into() => "a b c".into(),
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("field1".into(), term("a b c")),
+            ])
         );
 
         // Escaping
         assert_eq!(
             parse(columns, primary_column, r#"hello\ world"#),
-            hashmap!(
-                "primary".into() => r#"hello\ world"#.into(),
-            )
+            HashMap::from([("primary".into(), term(r#"hello\ world"#))])
         );
         assert_eq!(
             parse(columns, primary_column, r#"hello \%field1:world"#),
-            hashmap!(
-                "primary".into() => "hello %field1:world".into(),
-            )
+            HashMap::from([("primary".into(), term("hello %field1:world"))])
         );
         assert_eq!(
             parse(columns, primary_column, r#"hello %field1:"a\"b""#),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "field1".into() => r#"a"b"#.into(),
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("field1".into(), term(r#"a"b"#)),
+            ])
         );
         assert_eq!(
             parse(columns, primary_column, r#"%field1:hello\ world"#),
-            hashmap!(
-                "field1".into() => r#"hello\ world"#.into(),
-            )
+            HashMap::from([("field1".into(), term(r#"hello\ world"#))])
         );
         assert_eq!(
             parse(columns, primary_column, r#"%field1:"hello\ world""#),
-            hashmap!(
-                "field1".into() => r#"hello\ world"#.into(),
-            )
+            HashMap::from([("field1".into(), term(r#"hello\ world"#))])
         );
         assert_eq!(
             parse(columns, primary_column, r#"\bfoo\b"#),
-            hashmap!(
-                "primary".into() => r#"\bfoo\b"#.into(),
-            )
+            HashMap::from([("primary".into(), term(r#"\bfoo\b"#))])
         );
 
         // Prefix
         assert_eq!(
             parse(columns, primary_column, "hello %anot:abc"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "another".into() => "abc".into(),
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("another".into(), term("abc")),
+            ])
         );
         assert_eq!(
             parse(columns, primary_column, "hello %ano:abc"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "anode".into() => "abc".into()
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("anode".into(), term("abc")),
+            ])
         );
         assert_eq!(
             parse(columns, primary_column, "hello %field1:xyz %fie:abc"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "field1".into() => "xyz abc".into()
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("field1".into(), terms(&["xyz", "abc"])),
+            ])
         );
         assert_eq!(
             parse(columns, primary_column, "hello %fie:abc"),
-            hashmap!(
-                "primary".into() => "hello".into(),
-                "field1".into() => "abc".into()
-            )
+            HashMap::from([
+                ("primary".into(), term("hello")),
+                ("field1".into(), term("abc")),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_negated_query_test() {
+        let columns = &["primary".into(), "field1".into()];
+        let primary_column = 0;
+
+        // A leading '!' on a bare word negates it.
+        assert_eq!(
+            parse(columns, primary_column, "hello !world"),
+            HashMap::from([(
+                "primary".into(),
+                vec![
+                    FieldQuery {
+                        predicate: Predicate::Fuzzy("hello".into()),
+                        negate: false,
+                        mode: MatchMode::Fuzzy,
+                    },
+                    FieldQuery {
+                        predicate: Predicate::Fuzzy("world".into()),
+                        negate: true,
+                        mode: MatchMode::Fuzzy,
+                    },
+                ]
+            )])
+        );
+
+        // Same, scoped to a named field.
+        assert_eq!(
+            parse(columns, primary_column, "%field1:!foo"),
+            HashMap::from([("field1".into(), negated("foo"))])
+        );
+
+        // A lone '!' has nothing to negate, so it is kept as a literal
+        // pattern instead of becoming an empty negated term.
+        assert_eq!(
+            parse(columns, primary_column, "%field1:!"),
+            HashMap::from([("field1".into(), term("!"))])
+        );
+
+        // '\!' escapes to a literal bang, even when leading.
+        assert_eq!(
+            parse(columns, primary_column, r#"\!foo"#),
+            HashMap::from([("primary".into(), term("!foo"))])
+        );
+
+        // Nucleo's own `!` negation prefix is reconstructed from the parsed
+        // terms when building the pattern string passed to the matcher.
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "hello !world")["primary"]),
+            "hello !world"
+        );
+    }
+
+    #[test]
+    fn parse_comparison_query_test() {
+        let columns = &["primary".into(), "size".into(), "modified".into()];
+        let primary_column = 0;
+
+        assert_eq!(
+            parse(columns, primary_column, "%size:>100"),
+            HashMap::from([(
+                "size".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Compare(CompareOp::Gt, "100".into()),
+                    negate: false,
+                    mode: MatchMode::Fuzzy,
+                }]
+            )])
+        );
+        assert_eq!(
+            parse(columns, primary_column, "%size:<=1M"),
+            HashMap::from([(
+                "size".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Compare(CompareOp::Le, "1M".into()),
+                    negate: false,
+                    mode: MatchMode::Fuzzy,
+                }]
+            )])
+        );
+        assert_eq!(
+            parse(columns, primary_column, "%modified:2024-01..2024-06"),
+            HashMap::from([(
+                "modified".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Range("2024-01".into(), "2024-06".into()),
+                    negate: false,
+                    mode: MatchMode::Fuzzy,
+                }]
+            )])
+        );
+
+        // Escaped operators remain literal fuzzy text.
+        assert_eq!(
+            parse(columns, primary_column, r#"%size:\>100"#),
+            HashMap::from([("size".into(), term(r#"\>100"#))])
+        );
+
+        // An unanchored '..' with nothing on one side isn't a range.
+        assert_eq!(
+            parse(columns, primary_column, "%size:100.."),
+            HashMap::from([("size".into(), term("100.."))])
+        );
+
+        // Compare/range terms are never sent to nucleo.
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "%size:>100")["size"]),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_alternation_query_test() {
+        let columns = &["primary".into(), "ext".into()];
+        let primary_column = 0;
+
+        assert_eq!(
+            parse(columns, primary_column, "%ext:rs|toml|md"),
+            HashMap::from([(
+                "ext".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Alternatives(vec![
+                        "rs".into(),
+                        "toml".into(),
+                        "md".into()
+                    ]),
+                    negate: false,
+                    mode: MatchMode::Fuzzy,
+                }]
+            )])
+        );
+
+        // A quoted segment keeps its pipe literal.
+        assert_eq!(
+            parse(columns, primary_column, r#"%ext:"a|b""#),
+            HashMap::from([("ext".into(), term("a|b"))])
+        );
+
+        // '\|' escapes to a literal pipe.
+        assert_eq!(
+            parse(columns, primary_column, r#"%ext:a\|b"#),
+            HashMap::from([("ext".into(), term("a|b"))])
+        );
+
+        // A single pattern with no '|' is unaffected.
+        assert_eq!(
+            parse(columns, primary_column, "%ext:rs"),
+            HashMap::from([("ext".into(), term("rs"))])
+        );
+
+        // Alternatives are never sent to nucleo.
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "%ext:rs|toml|md")["ext"]),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_exact_mode_query_test() {
+        let columns = &["primary".into(), "name".into()];
+        let primary_column = 0;
+
+        // `=` is an alternative field separator that requests exact matching.
+        assert_eq!(
+            parse(columns, primary_column, "%name=exact-value"),
+            HashMap::from([(
+                "name".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Fuzzy("exact-value".into()),
+                    negate: false,
+                    mode: MatchMode::Exact,
+                }]
+            )])
+        );
+
+        // `:` still requests the default fuzzy matching.
+        assert_eq!(
+            parse(columns, primary_column, "%name:loose-value"),
+            HashMap::from([("name".into(), term("loose-value"))])
+        );
+
+        // '\=' escapes to a literal equals sign inside a value.
+        assert_eq!(
+            parse(columns, primary_column, r#"%name:a\=b"#),
+            HashMap::from([("name".into(), term("a=b"))])
+        );
+
+        // Exact-mode terms are never sent to nucleo.
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "%name=exact-value")["name"]),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_contains_mode_query_test() {
+        let columns = &["primary".into(), "name".into()];
+        let primary_column = 0;
+
+        // A leading '~' on a scoped field's value requests substring
+        // (non-fuzzy) matching.
+        assert_eq!(
+            parse(columns, primary_column, "%name:~sub-value"),
+            HashMap::from([(
+                "name".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Fuzzy("sub-value".into()),
+                    negate: false,
+                    mode: MatchMode::Contains,
+                }]
+            )])
+        );
+
+        // '!' negation still applies on top of the contains sigil.
+        assert_eq!(
+            parse(columns, primary_column, "%name:!~sub-value"),
+            HashMap::from([(
+                "name".into(),
+                vec![FieldQuery {
+                    predicate: Predicate::Fuzzy("sub-value".into()),
+                    negate: true,
+                    mode: MatchMode::Contains,
+                }]
+            )])
+        );
+
+        // '\~' escapes to a literal tilde inside a value, not the sigil.
+        assert_eq!(
+            parse(columns, primary_column, r#"%name:\~sub-value"#),
+            HashMap::from([("name".into(), term("~sub-value"))])
+        );
+
+        // On the primary/unnamed column '~' is kept literal.
+        assert_eq!(
+            parse(columns, primary_column, "~value"),
+            HashMap::from([("primary".into(), term("~value"))])
+        );
+
+        // Contains-mode terms are never sent to nucleo.
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "%name:~sub-value")["name"]),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_primary_column_operators_stay_literal_test() {
+        // Comparison/range/alternation operators are only recognized inside
+        // an explicit `%field:`; on the primary/unnamed column (e.g. a
+        // live-grep pattern) they're unstructured text and must still reach
+        // nucleo via `pattern_text`.
+        let columns = &["primary".into(), "size".into()];
+        let primary_column = 0;
+
+        assert_eq!(
+            parse(columns, primary_column, "a|b"),
+            HashMap::from([("primary".into(), term("a|b"))])
+        );
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, "a|b")["primary"]),
+            "a|b"
+        );
+
+        assert_eq!(
+            parse(columns, primary_column, "a..b"),
+            HashMap::from([("primary".into(), term("a..b"))])
+        );
+        assert_eq!(
+            parse(columns, primary_column, ">100"),
+            HashMap::from([("primary".into(), term(">100"))])
+        );
+        assert_eq!(
+            pattern_text(&parse(columns, primary_column, ">100")["primary"]),
+            ">100"
         );
     }
 }